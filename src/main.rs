@@ -1,8 +1,10 @@
+use chrono::{DateTime, Duration, SecondsFormat, Utc};
 use clap::{crate_authors, crate_name, crate_version, App, AppSettings, Arg, SubCommand};
-use serde_json::{from_value, to_value, Map, Value};
+use serde::Serialize;
+use serde_json::{Map, Value};
 use std::{
     fs::{read_to_string, write},
-    io::{Error, ErrorKind},
+    io::{self, Error, ErrorKind},
     path::{Path, PathBuf},
     process::exit,
 };
@@ -16,75 +18,669 @@ fn file_path() -> PathBuf {
     }
 }
 
-fn load_json() -> Result<Map<String, Value>, Error> {
+// Reserved single-key shape used to carry an expiration alongside a stored value. A user value
+// that merely happens to be an object is never mistaken for this, because a wrapper has exactly
+// one key — the reserved sentinel — whose value is an object holding `expires_at` and `value`.
+const EXPIRY_KEY: &str = "__kv_expiry__";
+
+// If `value` is a TTL wrapper, return its inner value and expiration instant; otherwise `None`.
+fn as_wrapper(value: &Value) -> Option<(&Value, DateTime<Utc>)> {
+    let obj = value.as_object()?;
+    if obj.len() != 1 {
+        return None;
+    }
+    let inner = obj.get(EXPIRY_KEY)?.as_object()?;
+    let expires = inner.get("expires_at")?.as_str()?;
+    let value = inner.get("value")?;
+    let ts = DateTime::parse_from_rfc3339(expires).ok()?.with_timezone(&Utc);
+    Some((value, ts))
+}
+
+// Unwrap a TTL wrapper to its stored value, leaving plain values untouched.
+fn unwrap_value(value: &Value) -> &Value {
+    match as_wrapper(value) {
+        Some((inner, _)) => inner,
+        None => value,
+    }
+}
+
+// Wrap a value so it expires at the given instant.
+fn make_wrapper(value: Value, expires: DateTime<Utc>) -> Value {
+    let mut inner = Map::new();
+    inner.insert(
+        "expires_at".to_string(),
+        Value::String(expires.to_rfc3339_opts(SecondsFormat::Secs, true)),
+    );
+    inner.insert("value".to_string(), value);
+    let mut wrapper = Map::new();
+    wrapper.insert(EXPIRY_KEY.to_string(), Value::Object(inner));
+    Value::Object(wrapper)
+}
+
+fn load_json(fmt: &Fmt) -> Result<Map<String, Value>, Error> {
     let json = read_to_string(file_path())?;
     let value: Value = serde_json::from_str(&json)?;
+    let map = match value {
+        Value::Object(m) => m,
+        _ => {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                "Data in file was not an object.",
+            ))
+        }
+    };
+    // Lazily evict any entry whose expiry is in the past, rewriting the file if anything dropped
+    // so expired keys never surface to `get`/`list`/`complete_keys`.
+    let now = Utc::now();
+    let before = map.len();
+    let mut kept = Map::new();
+    for (k, v) in map {
+        let live = match as_wrapper(&v) {
+            Some((_, expires)) => expires > now,
+            None => true,
+        };
+        if live {
+            kept.insert(k, v);
+        }
+    }
+    if kept.len() != before {
+        if let Ok(serialized) = fmt.serialize(&Value::Object(kept.clone())) {
+            let _ = write(file_path(), serialized);
+        }
+    }
+    Ok(kept)
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Mode {
+    Plain,
+    Json,
+    JsonPretty,
+}
+
+// Serialization controls shared by the printed output of `get`/`list` and what `save_json` writes
+// to disk, so the on-disk `kv.json` can be made human-diffable with the same flags.
+#[derive(Clone)]
+struct Fmt {
+    mode: Mode,
+    indent: String,
+    space: String,
+    newline: String,
+}
+
+impl Fmt {
+    // Serialize a whole `Value`. `plain`/`json` both emit compact JSON (so the file stays valid);
+    // `json-pretty` uses the configurable formatter below.
+    fn serialize(&self, value: &Value) -> Result<String, Error> {
+        match self.mode {
+            Mode::JsonPretty => {
+                let mut buf = Vec::new();
+                let mut ser =
+                    serde_json::Serializer::with_formatter(&mut buf, PrettyFormatter::new(self));
+                value
+                    .serialize(&mut ser)
+                    .map_err(|e| Error::other(e.to_string()))?;
+                String::from_utf8(buf).map_err(|e| Error::other(e.to_string()))
+            }
+            _ => Ok(value.to_string()),
+        }
+    }
+}
+
+// A JSON pretty-printer whose indent, inter-token newline, and post-colon space strings are all
+// configurable, modelled on RedisJSON's INDENT/NEWLINE/SPACE arguments.
+struct PrettyFormatter<'a> {
+    current_indent: usize,
+    has_value: bool,
+    indent: &'a [u8],
+    newline: &'a [u8],
+    space: &'a [u8],
+}
+
+impl<'a> PrettyFormatter<'a> {
+    fn new(fmt: &'a Fmt) -> Self {
+        PrettyFormatter {
+            current_indent: 0,
+            has_value: false,
+            indent: fmt.indent.as_bytes(),
+            newline: fmt.newline.as_bytes(),
+            space: fmt.space.as_bytes(),
+        }
+    }
+
+    fn write_indent<W: ?Sized + io::Write>(&self, writer: &mut W) -> io::Result<()> {
+        for _ in 0..self.current_indent {
+            writer.write_all(self.indent)?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a> serde_json::ser::Formatter for PrettyFormatter<'a> {
+    fn begin_array<W: ?Sized + io::Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        self.current_indent += 1;
+        self.has_value = false;
+        writer.write_all(b"[")
+    }
+
+    fn end_array<W: ?Sized + io::Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        self.current_indent -= 1;
+        if self.has_value {
+            writer.write_all(self.newline)?;
+            self.write_indent(writer)?;
+        }
+        writer.write_all(b"]")
+    }
+
+    fn begin_array_value<W: ?Sized + io::Write>(
+        &mut self,
+        writer: &mut W,
+        first: bool,
+    ) -> io::Result<()> {
+        if !first {
+            writer.write_all(b",")?;
+        }
+        writer.write_all(self.newline)?;
+        self.write_indent(writer)
+    }
+
+    fn end_array_value<W: ?Sized + io::Write>(&mut self, _writer: &mut W) -> io::Result<()> {
+        self.has_value = true;
+        Ok(())
+    }
+
+    fn begin_object<W: ?Sized + io::Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        self.current_indent += 1;
+        self.has_value = false;
+        writer.write_all(b"{")
+    }
+
+    fn end_object<W: ?Sized + io::Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        self.current_indent -= 1;
+        if self.has_value {
+            writer.write_all(self.newline)?;
+            self.write_indent(writer)?;
+        }
+        writer.write_all(b"}")
+    }
+
+    fn begin_object_key<W: ?Sized + io::Write>(
+        &mut self,
+        writer: &mut W,
+        first: bool,
+    ) -> io::Result<()> {
+        if !first {
+            writer.write_all(b",")?;
+        }
+        writer.write_all(self.newline)?;
+        self.write_indent(writer)
+    }
+
+    fn begin_object_value<W: ?Sized + io::Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        writer.write_all(b":")?;
+        writer.write_all(self.space)
+    }
+
+    fn end_object_value<W: ?Sized + io::Write>(&mut self, _writer: &mut W) -> io::Result<()> {
+        self.has_value = true;
+        Ok(())
+    }
+}
+
+fn save_json(json: Value, fmt: &Fmt) -> Result<(), Error> {
+    write(file_path(), fmt.serialize(&json)?)
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "bool",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+fn type_mismatch(key: &str, expected: &str, value: &Value) -> Error {
+    Error::new(
+        ErrorKind::InvalidData,
+        format!("Key \"{}\" is a {}, expected {}.", key, type_name(value), expected),
+    )
+}
+
+// Typed accessors that coerce a stored `Value`, returning an `InvalidData` error naming the key
+// and the expected-vs-actual type. Used by `get --type` to validate a value's shape.
+fn get_str<'a>(key: &str, value: &'a Value) -> Result<&'a str, Error> {
+    value.as_str().ok_or_else(|| type_mismatch(key, "string", value))
+}
+
+fn get_bool(key: &str, value: &Value) -> Result<bool, Error> {
+    value.as_bool().ok_or_else(|| type_mismatch(key, "bool", value))
+}
+
+fn get_array<'a>(key: &str, value: &'a Value) -> Result<&'a Vec<Value>, Error> {
+    value.as_array().ok_or_else(|| type_mismatch(key, "array", value))
+}
+
+fn get_object<'a>(key: &str, value: &'a Value) -> Result<&'a Map<String, Value>, Error> {
+    value.as_object().ok_or_else(|| type_mismatch(key, "object", value))
+}
+
+// Validate that `value` coerces to the named type, surfacing a typed error if not.
+fn check_type(key: &str, ty: &str, value: &Value) -> Result<(), Error> {
+    match ty {
+        "string" => get_str(key, value).map(|_| ()),
+        "bool" => get_bool(key, value).map(|_| ()),
+        // `number` mirrors the predicate `set` uses, so any stored JSON number validates,
+        // not just the non-negative integers `as_u64` accepts.
+        "number" if value.is_number() => Ok(()),
+        "number" => Err(type_mismatch(key, "number", value)),
+        "array" => get_array(key, value).map(|_| ()),
+        "object" => get_object(key, value).map(|_| ()),
+        _ => Ok(()),
+    }
+}
+
+// Render a stored value for display: plain strings print unquoted (matching the original
+// `k -> v` output), everything else prints as compact JSON.
+fn render(value: &Value) -> String {
     match value {
-        Value::Object(m) => Ok(m),
-        _ => Err(Error::new(
-            ErrorKind::InvalidData,
-            "Data in file was not an object.",
-        )),
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+// Render a remaining duration as a compact, human-friendly string (e.g. `5m`, `2h`, `3d`).
+fn humanize(d: Duration) -> String {
+    let secs = d.num_seconds().max(0);
+    if secs >= 86400 {
+        format!("{}d", secs / 86400)
+    } else if secs >= 3600 {
+        format!("{}h", secs / 3600)
+    } else if secs >= 60 {
+        format!("{}m", secs / 60)
+    } else {
+        format!("{}s", secs)
     }
 }
 
-fn save_json(json: Value) -> Result<(), Error> {
-    write(file_path(), format!("{}", json))
+// Turn a raw command-line argument into a stored `Value` according to the requested type. `auto`
+// tries to parse the argument as JSON and falls back to a string literal if that fails.
+fn parse_value(raw: &str, ty: &str) -> Result<Value, Error> {
+    match ty {
+        "string" => Ok(Value::String(raw.to_string())),
+        "json" => serde_json::from_str(raw)
+            .map_err(|e| Error::new(ErrorKind::InvalidInput, format!("Invalid JSON: {}", e))),
+        "number" => {
+            let value: Value = serde_json::from_str(raw)
+                .map_err(|_| Error::new(ErrorKind::InvalidInput, format!("\"{}\" is not a number.", raw)))?;
+            if value.is_number() {
+                Ok(value)
+            } else {
+                Err(Error::new(ErrorKind::InvalidInput, format!("\"{}\" is not a number.", raw)))
+            }
+        }
+        "bool" => match raw {
+            "true" => Ok(Value::Bool(true)),
+            "false" => Ok(Value::Bool(false)),
+            _ => Err(Error::new(ErrorKind::InvalidInput, format!("\"{}\" is not a bool.", raw))),
+        },
+        _ => Ok(serde_json::from_str(raw).unwrap_or_else(|_| Value::String(raw.to_string()))),
+    }
 }
 
-fn get(key: &str) -> Result<String, Error> {
-    let mut json = load_json()?;
-    // we're not going to save this json so we can just remove the item. this avoids getting a
-    // reference we have to clone.
-    match json.remove(key) {
-        Some(v) => Ok(from_value::<String>(v)?),
-        None => Err(Error::new(
-            ErrorKind::NotFound,
-            format!("Key \"{}\" not found.", key),
-        )),
+fn not_found(path: &str) -> Error {
+    Error::new(
+        ErrorKind::NotFound,
+        format!("Key \"{}\" not found.", path),
+    )
+}
+
+// Split a path like `server.ports.0` or `$.server.ports[0]` into its segments. A leading `$`,
+// empty string, or lone `.` refers to the whole document and yields no segments.
+fn parse_path(path: &str) -> Vec<String> {
+    let trimmed = path.strip_prefix('$').unwrap_or(path);
+    let mut segments = vec![];
+    for part in trimmed.split('.') {
+        let mut rest = part;
+        while let Some(open) = rest.find('[') {
+            let name = &rest[..open];
+            if !name.is_empty() {
+                segments.push(name.to_string());
+            }
+            match rest[open..].find(']') {
+                Some(close) => {
+                    segments.push(rest[open + 1..open + close].to_string());
+                    rest = &rest[open + close + 1..];
+                }
+                None => {
+                    rest = "";
+                    break;
+                }
+            }
+        }
+        if !rest.is_empty() {
+            segments.push(rest.to_string());
+        }
     }
+    segments
 }
 
-fn set(key: &str, value: &str, force: bool) -> Result<String, Error> {
-    let mut json = load_json().unwrap_or_default();
-    if json.contains_key(key) && !force {
-        return Err(Error::new(
-            ErrorKind::AlreadyExists,
-            format!(
-                "Key \"{}\" already present. (Use --force to overwrite.)",
-                key
-            ),
-        ));
+// Walk a single segment into a container, optionally creating a missing object along the way.
+fn descend<'a>(current: &'a mut Value, seg: &str, create: bool) -> Result<&'a mut Value, Error> {
+    match current {
+        Value::Object(m) => {
+            if create && !m.contains_key(seg) {
+                m.insert(seg.to_string(), Value::Object(Map::new()));
+            }
+            m.get_mut(seg).ok_or_else(|| not_found(seg))
+        }
+        Value::Array(a) => {
+            let idx = seg
+                .parse::<usize>()
+                .map_err(|_| Error::new(ErrorKind::InvalidInput, format!("\"{}\" is not a valid array index.", seg)))?;
+            a.get_mut(idx).ok_or_else(|| not_found(seg))
+        }
+        _ => Err(not_found(seg)),
     }
-    json.insert(key.to_string(), to_value(value)?);
-    save_json(to_value(json)?)?;
-    Ok(format!("Key \"{}\" set to value \"{}\".", key, value))
 }
 
-fn delete(key: &str) -> Result<String, Error> {
-    let mut json = load_json()?;
-    if !json.contains_key(key) {
-        return Err(Error::new(
-            ErrorKind::NotFound,
-            format!("Key \"{}\" not found.", key),
-        ));
+fn get(path: &str, ty: Option<&str>, fmt: &Fmt) -> Result<String, Error> {
+    let root = Value::Object(load_json(fmt)?);
+    let segments = parse_path(path);
+    let mut current = &root;
+    for seg in &segments {
+        current = match current {
+            Value::Object(m) => unwrap_value(m.get(seg).ok_or_else(|| not_found(path))?),
+            Value::Array(a) => {
+                let idx = seg.parse::<usize>().map_err(|_| not_found(path))?;
+                unwrap_value(a.get(idx).ok_or_else(|| not_found(path))?)
+            }
+            _ => return Err(not_found(path)),
+        };
+    }
+    if let Some(ty) = ty {
+        check_type(path, ty, current)?;
+    }
+    match fmt.mode {
+        Mode::Plain => Ok(render(current)),
+        _ => fmt.serialize(current),
     }
-    json.remove(key);
-    save_json(to_value(json)?)?;
-    Ok(format!("Deleted key \"{}\".", key))
 }
 
-fn list() -> Result<String, Error> {
-    let json = load_json().unwrap_or_default();
-    Ok(if json.is_empty() {
-        "No keys found.".to_string()
+// Resolve the optional `--ttl`/`--expires-at` arguments into an absolute expiration instant.
+// `--expires-at` wins if both are supplied.
+fn resolve_expiry(ttl: Option<&str>, expires_at: Option<&str>) -> Result<Option<DateTime<Utc>>, Error> {
+    if let Some(ts) = expires_at {
+        let dt = DateTime::parse_from_rfc3339(ts)
+            .map_err(|e| Error::new(ErrorKind::InvalidInput, format!("Invalid --expires-at: {}", e)))?;
+        Ok(Some(dt.with_timezone(&Utc)))
+    } else if let Some(s) = ttl {
+        let secs = s
+            .parse::<i64>()
+            .map_err(|_| Error::new(ErrorKind::InvalidInput, format!("Invalid --ttl: \"{}\"", s)))?;
+        Ok(Some(Utc::now() + Duration::seconds(secs)))
     } else {
-        let mut items = vec![];
-        for (k, v) in json.into_iter() {
-            items.push(format!("{} -> {}", k, from_value::<String>(v)?));
+        Ok(None)
+    }
+}
+
+fn set(
+    path: &str,
+    value: &str,
+    ty: &str,
+    force: bool,
+    ttl: Option<&str>,
+    expires_at: Option<&str>,
+    fmt: &Fmt,
+) -> Result<String, Error> {
+    let segments = parse_path(path);
+    let mut parsed = parse_value(value, ty)?;
+    if let Some(expires) = resolve_expiry(ttl, expires_at)? {
+        // Expiry is tracked per top-level key: `load_json` only evicts the outer map, so allowing
+        // a wrapper on a nested leaf would leave an un-evictable value that never expires.
+        if segments.len() != 1 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "TTL can only be set on top-level keys.",
+            ));
+        }
+        parsed = make_wrapper(parsed, expires);
+    }
+    let mut root = Value::Object(load_json(fmt).unwrap_or_default());
+    if segments.is_empty() {
+        save_json(parsed, fmt)?;
+        return Ok(format!("Document set to value \"{}\".", value));
+    }
+    let (leaf, parents) = segments.split_last().unwrap();
+    let mut current = &mut root;
+    for seg in parents {
+        current = descend(current, seg, true)?;
+    }
+    match current {
+        Value::Object(m) => {
+            if m.contains_key(leaf) && !force {
+                return Err(Error::new(
+                    ErrorKind::AlreadyExists,
+                    format!("Key \"{}\" already present. (Use --force to overwrite.)", path),
+                ));
+            }
+            m.insert(leaf.to_string(), parsed);
+        }
+        Value::Array(a) => {
+            let idx = leaf.parse::<usize>().map_err(|_| {
+                Error::new(ErrorKind::InvalidInput, format!("\"{}\" is not a valid array index.", leaf))
+            })?;
+            if idx < a.len() {
+                if !force {
+                    return Err(Error::new(
+                        ErrorKind::AlreadyExists,
+                        format!("Key \"{}\" already present. (Use --force to overwrite.)", path),
+                    ));
+                }
+                a[idx] = parsed;
+            } else {
+                a.push(parsed);
+            }
         }
-        items.join("\n")
-    })
+        _ => return Err(not_found(path)),
+    }
+    save_json(root, fmt)?;
+    Ok(format!("Key \"{}\" set to value \"{}\".", path, value))
+}
+
+fn delete(path: &str, fmt: &Fmt) -> Result<String, Error> {
+    let mut root = Value::Object(load_json(fmt)?);
+    let segments = parse_path(path);
+    if segments.is_empty() {
+        root = Value::Object(Map::new());
+        save_json(root, fmt)?;
+        return Ok("Deleted whole document.".to_string());
+    }
+    let (leaf, parents) = segments.split_last().unwrap();
+    let mut current = &mut root;
+    for seg in parents {
+        current = descend(current, seg, false)?;
+    }
+    match current {
+        Value::Object(m) => {
+            if m.remove(leaf).is_none() {
+                return Err(not_found(path));
+            }
+        }
+        Value::Array(a) => {
+            let idx = leaf.parse::<usize>().map_err(|_| not_found(path))?;
+            if idx >= a.len() {
+                return Err(not_found(path));
+            }
+            a.remove(idx);
+        }
+        _ => return Err(not_found(path)),
+    }
+    save_json(root, fmt)?;
+    Ok(format!("Deleted key \"{}\".", path))
+}
+
+// Serialize a value for editing in a text buffer: plain strings are written unquoted, containers
+// are pretty-printed so they're comfortable to hand-edit.
+fn seed_buffer(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => serde_json::to_string_pretty(other).unwrap_or_else(|_| other.to_string()),
+    }
+}
+
+fn edit(path: &str, ty: &str, create: bool, fmt: &Fmt) -> Result<String, Error> {
+    let root = Value::Object(load_json(fmt).unwrap_or_default());
+    let segments = parse_path(path);
+
+    let mut current = Some(&root);
+    for seg in &segments {
+        current = match current {
+            Some(Value::Object(m)) => m.get(seg),
+            Some(Value::Array(a)) => seg.parse::<usize>().ok().and_then(|i| a.get(i)),
+            _ => None,
+        };
+    }
+    // Remember any existing expiry so an edit doesn't silently strip a key's TTL.
+    let (seed, keep_expiry) = match current {
+        Some(value) => (
+            seed_buffer(unwrap_value(value)),
+            as_wrapper(value).map(|(_, expires)| expires),
+        ),
+        None if create => (String::new(), None),
+        None => return Err(not_found(path)),
+    };
+
+    let tmp = std::env::temp_dir().join(format!("kv-edit-{}.json", std::process::id()));
+    write(&tmp, &seed)?;
+
+    let editor = std::env::var("EDITOR")
+        .or_else(|_| std::env::var("VISUAL"))
+        .unwrap_or_else(|_| "vi".to_string());
+    let status = std::process::Command::new(&editor).arg(&tmp).status()?;
+    if !status.success() {
+        let _ = std::fs::remove_file(&tmp);
+        return Err(Error::other(format!(
+            "Editor \"{}\" exited with a non-zero status; aborting.",
+            editor
+        )));
+    }
+
+    let edited = read_to_string(&tmp)?;
+    let _ = std::fs::remove_file(&tmp);
+
+    // Run the saved buffer through the same typed/JSON parsing as `set` (overwriting is implied),
+    // carrying the original expiry forward so editing a TTL'd key keeps it expiring.
+    let trimmed = edited.strip_suffix('\n').unwrap_or(&edited);
+    let expires_at = keep_expiry.map(|e| e.to_rfc3339_opts(SecondsFormat::Secs, true));
+    set(path, trimmed, ty, true, None, expires_at.as_deref(), fmt)?;
+    Ok(format!("Key \"{}\" updated.", path))
+}
+
+// Namespace-aware prefix test: `app` matches `app` and `app.db` but not `application`, so a prefix
+// only ever matches at a `.`-delimited boundary (or exactly).
+fn has_prefix(key: &str, prefix: &str) -> bool {
+    key.starts_with(prefix)
+        && (key.len() == prefix.len()
+            || prefix.ends_with('.')
+            || key[prefix.len()..].starts_with('.'))
+}
+
+// A minimal glob matcher supporting `*` (any run) and `?` (any single byte).
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let (p, t) = (pattern.as_bytes(), text.as_bytes());
+    let (mut pi, mut ti) = (0, 0);
+    let (mut star, mut mark) = (None, 0);
+    while ti < t.len() {
+        if pi < p.len() && (p[pi] == b'?' || p[pi] == t[ti]) {
+            pi += 1;
+            ti += 1;
+        } else if pi < p.len() && p[pi] == b'*' {
+            star = Some(pi);
+            mark = ti;
+            pi += 1;
+        } else if let Some(s) = star {
+            pi = s + 1;
+            mark += 1;
+            ti = mark;
+        } else {
+            return false;
+        }
+    }
+    while pi < p.len() && p[pi] == b'*' {
+        pi += 1;
+    }
+    pi == p.len()
+}
+
+// Collect the matching entries in sorted key order. The namespace match set is not a contiguous
+// run in byte order — a sibling whose boundary byte sorts below `.` (e.g. `app-legacy` between
+// `app` and `app.db`) splits it — so prefix mode scans every key rather than breaking early.
+fn filter_keys<'a>(
+    json: &'a Map<String, Value>,
+    prefix: Option<&str>,
+    glob: Option<&str>,
+) -> Vec<(&'a String, &'a Value)> {
+    let mut keys: Vec<&String> = json.keys().collect();
+    keys.sort();
+    let mut out = vec![];
+    match (glob, prefix) {
+        (Some(pat), _) => {
+            for k in keys {
+                if glob_match(pat, k) {
+                    out.push((k, &json[k]));
+                }
+            }
+        }
+        (None, Some(pre)) => {
+            for k in keys {
+                if has_prefix(k, pre) {
+                    out.push((k, &json[k]));
+                }
+            }
+        }
+        (None, None) => {
+            for k in keys {
+                out.push((k, &json[k]));
+            }
+        }
+    }
+    out
+}
+
+fn list(prefix: Option<&str>, glob: Option<&str>, fmt: &Fmt) -> Result<String, Error> {
+    let json = load_json(fmt).unwrap_or_default();
+    let entries = filter_keys(&json, prefix, glob);
+    match fmt.mode {
+        Mode::Plain => Ok(if entries.is_empty() {
+            "No keys found.".to_string()
+        } else {
+            let now = Utc::now();
+            let mut items = vec![];
+            for (k, v) in entries {
+                match as_wrapper(v) {
+                    Some((inner, expires)) => items.push(format!(
+                        "{} -> {} (expires in {})",
+                        k,
+                        render(inner),
+                        humanize(expires - now),
+                    )),
+                    None => items.push(format!("{} -> {}", k, render(v))),
+                }
+            }
+            items.join("\n")
+        }),
+        // Emit a real JSON object of the logical values so the output can be piped into `jq`.
+        _ => {
+            let unwrapped = entries
+                .into_iter()
+                .map(|(k, v)| (k.clone(), unwrap_value(v).clone()))
+                .collect();
+            fmt.serialize(&Value::Object(unwrapped))
+        }
+    }
 }
 
 fn complete_commands(app: App) -> Result<String, Error> {
@@ -98,11 +694,11 @@ fn complete_commands(app: App) -> Result<String, Error> {
         .join("\n"))
 }
 
-fn complete_keys() -> Result<String, Error> {
-    let json = load_json()?;
+fn complete_keys(prefix: Option<&str>, glob: Option<&str>, fmt: &Fmt) -> Result<String, Error> {
+    let json = load_json(fmt)?;
     let mut items = vec![];
-    for (k, v) in json.into_iter() {
-        items.push(format!("{}:{}", k, from_value::<String>(v)?));
+    for (k, v) in filter_keys(&json, prefix, glob) {
+        items.push(format!("{}:{}", k, render(unwrap_value(v))));
     }
     Ok(items.join("\n"))
 }
@@ -111,6 +707,40 @@ fn main() {
     let app = App::new(crate_name!())
         .version(crate_version!())
         .author(crate_authors!())
+        .arg(
+            Arg::with_name("format")
+                .short("o")
+                .long("format")
+                .global(true)
+                .takes_value(true)
+                .possible_values(&["plain", "json", "json-pretty"])
+                .default_value("plain")
+                .help("Output format for get/list and the on-disk file."),
+        )
+        .arg(
+            Arg::with_name("indent")
+                .long("indent")
+                .global(true)
+                .takes_value(true)
+                .default_value("  ")
+                .help("Indent string used by json-pretty."),
+        )
+        .arg(
+            Arg::with_name("space")
+                .long("space")
+                .global(true)
+                .takes_value(true)
+                .default_value(" ")
+                .help("String placed after colons by json-pretty."),
+        )
+        .arg(
+            Arg::with_name("newline")
+                .long("newline")
+                .global(true)
+                .takes_value(true)
+                .default_value("\n")
+                .help("Newline string used by json-pretty."),
+        )
         .subcommand(
             SubCommand::with_name("delete")
                 .about("Deletes key:value pairs.")
@@ -127,9 +757,51 @@ fn main() {
                     Arg::with_name("key")
                         .required(true)
                         .help("The key of the value to retrieve."),
+                )
+                .arg(
+                    Arg::with_name("type")
+                        .short("t")
+                        .long("type")
+                        .takes_value(true)
+                        .possible_values(&["string", "number", "bool", "array", "object"])
+                        .help("Fail unless the value has this type."),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("edit")
+                .about("Opens a value in $EDITOR.")
+                .arg(Arg::with_name("key").required(true).help("The key to edit."))
+                .arg(
+                    Arg::with_name("type")
+                        .short("t")
+                        .long("type")
+                        .takes_value(true)
+                        .possible_values(&["string", "number", "bool", "json", "auto"])
+                        .default_value("auto")
+                        .help("How to interpret the edited value."),
+                )
+                .arg(
+                    Arg::with_name("create")
+                        .short("c")
+                        .long("create")
+                        .help("Allow editing a key that does not yet exist."),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("list")
+                .about("Lists all key:value pairs.")
+                .arg(
+                    Arg::with_name("prefix")
+                        .help("Only list keys under this namespace prefix."),
+                )
+                .arg(
+                    Arg::with_name("glob")
+                        .long("glob")
+                        .takes_value(true)
+                        .conflicts_with("prefix")
+                        .help("Only list keys matching this glob pattern."),
                 ),
         )
-        .subcommand(SubCommand::with_name("list").about("Lists all key:value pairs."))
         .subcommand(
             SubCommand::with_name("set")
                 .about("Sets a value for a key.")
@@ -139,6 +811,28 @@ fn main() {
                         .required(true)
                         .help("The value of the key."),
                 )
+                .arg(
+                    Arg::with_name("type")
+                        .short("t")
+                        .long("type")
+                        .takes_value(true)
+                        .possible_values(&["string", "number", "bool", "json", "auto"])
+                        .default_value("auto")
+                        .help("How to interpret the value."),
+                )
+                .arg(
+                    Arg::with_name("ttl")
+                        .long("ttl")
+                        .takes_value(true)
+                        .help("Expire the key after this many seconds."),
+                )
+                .arg(
+                    Arg::with_name("expires-at")
+                        .long("expires-at")
+                        .takes_value(true)
+                        .conflicts_with("ttl")
+                        .help("Expire the key at this RFC3339 timestamp."),
+                )
                 .arg(
                     Arg::with_name("force")
                         .short("f")
@@ -147,18 +841,55 @@ fn main() {
                 ),
         )
         .subcommand(SubCommand::with_name("complete-commands").setting(AppSettings::Hidden))
-        .subcommand(SubCommand::with_name("complete-keys").setting(AppSettings::Hidden));
-    match match app.clone().get_matches().subcommand() {
-        ("delete", Some(sub)) => delete(sub.value_of("key").unwrap()),
-        ("get", Some(sub)) => get(sub.value_of("key").unwrap()),
-        ("list", _) => list(),
+        .subcommand(
+            SubCommand::with_name("complete-keys")
+                .setting(AppSettings::Hidden)
+                .arg(Arg::with_name("prefix"))
+                .arg(
+                    Arg::with_name("glob")
+                        .long("glob")
+                        .takes_value(true)
+                        .conflicts_with("prefix"),
+                ),
+        );
+    let matches = app.clone().get_matches();
+    let fmt = Fmt {
+        mode: match matches.value_of("format").unwrap() {
+            "json" => Mode::Json,
+            "json-pretty" => Mode::JsonPretty,
+            _ => Mode::Plain,
+        },
+        indent: matches.value_of("indent").unwrap().to_string(),
+        space: matches.value_of("space").unwrap().to_string(),
+        newline: matches.value_of("newline").unwrap().to_string(),
+    };
+    match match matches.subcommand() {
+        ("delete", Some(sub)) => delete(sub.value_of("key").unwrap(), &fmt),
+        ("get", Some(sub)) => get(sub.value_of("key").unwrap(), sub.value_of("type"), &fmt),
+        ("edit", Some(sub)) => edit(
+            sub.value_of("key").unwrap(),
+            sub.value_of("type").unwrap(),
+            sub.is_present("create"),
+            &fmt,
+        ),
+        ("list", sub) => {
+            let sub = sub.unwrap();
+            list(sub.value_of("prefix"), sub.value_of("glob"), &fmt)
+        }
         ("set", Some(sub)) => set(
             sub.value_of("key").unwrap(),
             sub.value_of("value").unwrap(),
+            sub.value_of("type").unwrap(),
             sub.is_present("force"),
+            sub.value_of("ttl"),
+            sub.value_of("expires-at"),
+            &fmt,
         ),
         ("complete-commands", _) => complete_commands(app),
-        ("complete-keys", _) => complete_keys(),
+        ("complete-keys", sub) => {
+            let sub = sub.unwrap();
+            complete_keys(sub.value_of("prefix"), sub.value_of("glob"), &fmt)
+        }
         _ => Ok(String::new()),
     } {
         Ok(msg) => println!("{}", msg),